@@ -46,6 +46,9 @@ class JobInput(ApiModel):
 
     type: Literal["folder", "zip"] = Field(default="folder", description="Source payload kind")
     path: str = Field(..., min_length=1, description="Relative path inside storage root")
+    meta_path: Optional[str] = Field(
+        default=None, description="Relative path of the uploaded meta.json sidecar"
+    )
 
 
 class JobParams(ApiModel):
@@ -469,6 +472,7 @@ def _build_state(job_id: str, record: JobRecord) -> JobState:
         metadata={
             "title": record.payload.title,
             "volume": record.payload.volume,
+            "metaPath": record.payload.input.meta_path,
         },
     )
 def _estimate_total_frames(payload: JobCreate) -> int:
@@ -1,4 +1,5 @@
 mod doublepage;
+mod localjobs;
 mod manga;
 mod notion;
 
@@ -7,6 +8,7 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 use tauri::{async_runtime, Emitter, Manager};
 
 use rusqlite::{params, Connection};
@@ -352,6 +354,29 @@ fn validate_manga_artifact(
     manga::validate_artifact(request).map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+fn enqueue_local_pipeline(
+    queue: tauri::State<Arc<localjobs::LocalJobQueue>>,
+    config: localjobs::LocalPipelineConfig,
+) -> Result<localjobs::LocalJobRecord, String> {
+    queue.enqueue(config).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn list_local_jobs(
+    queue: tauri::State<Arc<localjobs::LocalJobQueue>>,
+) -> Result<Vec<localjobs::LocalJobRecord>, String> {
+    queue.list().map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn cancel_local_job(
+    queue: tauri::State<Arc<localjobs::LocalJobQueue>>,
+    job_id: String,
+) -> Result<localjobs::LocalJobRecord, String> {
+    queue.cancel(&job_id).map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 fn read_template_file(path: String) -> Result<String, String> {
     let resolved = PathBuf::from(&path);
@@ -900,6 +925,12 @@ pub fn run() {
             app.manage(AppState {
                 db_path: db_path.clone(),
             });
+            let local_jobs = Arc::new(localjobs::LocalJobQueue::new(
+                db_path.clone(),
+                Some(app.handle().clone()),
+            ));
+            local_jobs.start();
+            app.manage(local_jobs);
             // Notion: use SQLite-backed store and HTTP adapter when enabled.
             #[cfg(feature = "notion-sqlite")]
             {
@@ -943,6 +974,9 @@ pub fn run() {
             cancel_manga_job,
             download_manga_artifact,
             validate_manga_artifact,
+            enqueue_local_pipeline,
+            list_local_jobs,
+            cancel_local_job,
             read_template_file,
             // Notion Import M1 (skeleton)
             notion::commands::notion_start_oauth_session,
@@ -1054,6 +1088,9 @@ fn initialize_database(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     if let Err(err) = SqliteTokenStore::ensure_schema(path) {
         eprintln!("[notion] failed to ensure notion_tokens schema: {}", err);
     }
+    if let Err(err) = localjobs::ensure_schema(path) {
+        eprintln!("[localjobs] failed to ensure local_jobs schema: {}", err);
+    }
     Ok(())
 }
 
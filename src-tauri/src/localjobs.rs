@@ -0,0 +1,936 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::doublepage::{self, SplitCommandOptions, SplitError, SplitProgress};
+use crate::manga::{
+    self, RenameError, RenameOptions, RenameSplitOptions, RenameSplitSummary, UploadError,
+    UploadProgress, UploadProgressStage,
+};
+
+const WORKER_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+static JOB_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalPipelineConfig {
+    pub directory: PathBuf,
+    #[serde(default = "manga::default_pad")]
+    pub pad: usize,
+    #[serde(default = "manga::default_extension")]
+    pub target_extension: String,
+    #[serde(default)]
+    pub archive_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalJobStatus {
+    Pending,
+    Running,
+    Canceling,
+    Completed,
+    Failed,
+    Canceled,
+}
+
+impl LocalJobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LocalJobStatus::Pending => "pending",
+            LocalJobStatus::Running => "running",
+            LocalJobStatus::Canceling => "canceling",
+            LocalJobStatus::Completed => "completed",
+            LocalJobStatus::Failed => "failed",
+            LocalJobStatus::Canceled => "canceled",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(LocalJobStatus::Pending),
+            "running" => Some(LocalJobStatus::Running),
+            "canceling" => Some(LocalJobStatus::Canceling),
+            "completed" => Some(LocalJobStatus::Completed),
+            "failed" => Some(LocalJobStatus::Failed),
+            "canceled" => Some(LocalJobStatus::Canceled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalJobRecord {
+    pub id: String,
+    pub status: LocalJobStatus,
+    pub config: LocalPipelineConfig,
+    pub created_at: i64,
+    pub started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+    pub workspace: Option<PathBuf>,
+    pub archive_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+/// Existing split/upload progress payload tagged with the local job it
+/// belongs to, so listeners can tell background jobs from interactive runs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalJobProgress<T> {
+    local_job_id: String,
+    #[serde(flatten)]
+    progress: T,
+}
+
+#[derive(Debug)]
+pub enum LocalJobError {
+    Database(rusqlite::Error),
+    Serialization(serde_json::Error),
+    Split(SplitError),
+    Rename(RenameError),
+    Archive(UploadError),
+    WorkspaceMissing(PathBuf),
+    ArchiveExists(PathBuf),
+    InvalidStatus(String),
+    NotFound(String),
+    NotCancelable(String),
+    Canceled,
+}
+
+impl fmt::Display for LocalJobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocalJobError::Database(err) => write!(f, "database error: {}", err),
+            LocalJobError::Serialization(err) => write!(f, "invalid job config: {}", err),
+            LocalJobError::Split(err) => write!(f, "split failed: {}", err),
+            LocalJobError::Rename(err) => write!(f, "rename failed: {}", err),
+            LocalJobError::Archive(err) => write!(f, "archive failed: {}", err),
+            LocalJobError::WorkspaceMissing(path) => {
+                write!(f, "split produced no workspace for {}", path.display())
+            }
+            LocalJobError::ArchiveExists(path) => {
+                write!(f, "archive already exists: {}", path.display())
+            }
+            LocalJobError::InvalidStatus(status) => {
+                write!(f, "unknown local job status: {}", status)
+            }
+            LocalJobError::NotFound(id) => write!(f, "local job not found: {}", id),
+            LocalJobError::NotCancelable(id) => {
+                write!(f, "local job already finished: {}", id)
+            }
+            LocalJobError::Canceled => write!(f, "job canceled"),
+        }
+    }
+}
+
+impl std::error::Error for LocalJobError {}
+
+impl From<rusqlite::Error> for LocalJobError {
+    fn from(value: rusqlite::Error) -> Self {
+        LocalJobError::Database(value)
+    }
+}
+
+impl From<serde_json::Error> for LocalJobError {
+    fn from(value: serde_json::Error) -> Self {
+        LocalJobError::Serialization(value)
+    }
+}
+
+impl From<SplitError> for LocalJobError {
+    fn from(value: SplitError) -> Self {
+        LocalJobError::Split(value)
+    }
+}
+
+impl From<RenameError> for LocalJobError {
+    fn from(value: RenameError) -> Self {
+        LocalJobError::Rename(value)
+    }
+}
+
+impl From<UploadError> for LocalJobError {
+    fn from(value: UploadError) -> Self {
+        LocalJobError::Archive(value)
+    }
+}
+
+pub fn ensure_schema(path: &Path) -> Result<(), String> {
+    let conn = Connection::open(path).map_err(|err| err.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS local_jobs (
+            id TEXT PRIMARY KEY,
+            status TEXT NOT NULL,
+            config_json TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            started_at INTEGER NULL,
+            ended_at INTEGER NULL,
+            workspace TEXT NULL,
+            archive_path TEXT NULL,
+            error TEXT NULL
+        )",
+        [],
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+struct RunningJob {
+    id: String,
+    cancel: Arc<AtomicBool>,
+}
+
+struct PipelineOutput {
+    workspace: PathBuf,
+    archive_path: PathBuf,
+}
+
+pub struct LocalJobQueue {
+    db_path: PathBuf,
+    app: Option<AppHandle>,
+    running: Mutex<Option<RunningJob>>,
+    wake: Mutex<Option<Sender<()>>>,
+}
+
+impl LocalJobQueue {
+    pub fn new(db_path: PathBuf, app: Option<AppHandle>) -> Self {
+        Self {
+            db_path,
+            app,
+            running: Mutex::new(None),
+            wake: Mutex::new(None),
+        }
+    }
+
+    /// Re-queues jobs interrupted by a previous shutdown and spawns the single
+    /// worker thread that drains pending jobs in creation order.
+    pub fn start(self: &Arc<Self>) {
+        match self.recover_interrupted() {
+            Ok(count) if count > 0 => {
+                eprintln!("[localjobs] re-queued {} interrupted job(s)", count);
+            }
+            Ok(_) => {}
+            Err(err) => eprintln!("[localjobs] failed to recover jobs: {}", err),
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        *self.wake.lock().unwrap() = Some(sender);
+
+        let queue = Arc::clone(self);
+        thread::spawn(move || loop {
+            match queue.run_next() {
+                Ok(Some(_)) => continue,
+                Ok(None) => {}
+                Err(err) => {
+                    eprintln!("[localjobs] worker error: {}", err);
+                    thread::sleep(WORKER_ERROR_BACKOFF);
+                    continue;
+                }
+            }
+            if receiver.recv().is_err() {
+                break;
+            }
+        });
+    }
+
+    pub fn enqueue(&self, config: LocalPipelineConfig) -> Result<LocalJobRecord, LocalJobError> {
+        let created_at = now_ms();
+        let id = format!(
+            "local-{}-{}",
+            created_at,
+            JOB_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        );
+        let config_json = serde_json::to_string(&config)?;
+
+        let conn = self.open()?;
+        conn.execute(
+            "INSERT INTO local_jobs (id, status, config_json, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                id,
+                LocalJobStatus::Pending.as_str(),
+                config_json,
+                created_at
+            ],
+        )?;
+
+        self.wake();
+        self.load(&id)?
+            .ok_or_else(|| LocalJobError::NotFound(id.clone()))
+    }
+
+    pub fn list(&self) -> Result<Vec<LocalJobRecord>, LocalJobError> {
+        let conn = self.open()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM local_jobs ORDER BY created_at, rowid",
+            RECORD_COLUMNS
+        ))?;
+        let rows = stmt.query_map([], read_row)?;
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(decode_row(row?)?);
+        }
+        Ok(records)
+    }
+
+    pub fn load(&self, job_id: &str) -> Result<Option<LocalJobRecord>, LocalJobError> {
+        let conn = self.open()?;
+        let row = conn
+            .query_row(
+                &format!("SELECT {} FROM local_jobs WHERE id = ?1", RECORD_COLUMNS),
+                [job_id],
+                read_row,
+            )
+            .optional()?;
+        row.map(decode_row).transpose()
+    }
+
+    /// Pending jobs are canceled immediately. A running job is marked
+    /// `Canceling` and stops at the next stage boundary; an archive finished
+    /// after the request is discarded.
+    pub fn cancel(&self, job_id: &str) -> Result<LocalJobRecord, LocalJobError> {
+        let canceled_pending = {
+            let running = self.running.lock().unwrap();
+            let conn = self.open()?;
+            let updated = conn.execute(
+                "UPDATE local_jobs SET status = ?2, ended_at = ?3 WHERE id = ?1 AND status = ?4",
+                params![
+                    job_id,
+                    LocalJobStatus::Canceled.as_str(),
+                    now_ms(),
+                    LocalJobStatus::Pending.as_str()
+                ],
+            )?;
+
+            if updated > 0 {
+                true
+            } else if let Some(job) = running.as_ref().filter(|job| job.id == job_id) {
+                job.cancel.store(true, Ordering::SeqCst);
+                conn.execute(
+                    "UPDATE local_jobs SET status = ?2 WHERE id = ?1 AND status = ?3",
+                    params![
+                        job_id,
+                        LocalJobStatus::Canceling.as_str(),
+                        LocalJobStatus::Running.as_str()
+                    ],
+                )?;
+                false
+            } else if self.load(job_id)?.is_some() {
+                return Err(LocalJobError::NotCancelable(job_id.to_string()));
+            } else {
+                return Err(LocalJobError::NotFound(job_id.to_string()));
+            }
+        };
+
+        if canceled_pending {
+            self.emit_final(
+                job_id,
+                UploadProgressStage::Failed,
+                "作业已取消".to_string(),
+            );
+        }
+
+        self.load(job_id)?
+            .ok_or_else(|| LocalJobError::NotFound(job_id.to_string()))
+    }
+
+    /// Claims the oldest pending job and runs it to completion. Returns `None`
+    /// when the queue is empty.
+    pub(crate) fn run_next(&self) -> Result<Option<LocalJobRecord>, LocalJobError> {
+        let Some((record, cancel)) = self.claim_next()? else {
+            return Ok(None);
+        };
+
+        let result = self.execute_pipeline(&record, &cancel);
+
+        // Release the running slot before any fallible bookkeeping so a
+        // failed status write can never leave a stale cancel target behind.
+        let cancel_requested = {
+            let mut running = self.running.lock().unwrap();
+            running.take();
+            cancel.load(Ordering::SeqCst)
+        };
+        // Once cancel was requested the job ends as canceled, whatever the
+        // pipeline returned in the meantime.
+        let result = match result {
+            Ok(output) if cancel_requested => {
+                fs::remove_file(&output.archive_path).ok();
+                Err(LocalJobError::Canceled)
+            }
+            Err(_) if cancel_requested => Err(LocalJobError::Canceled),
+            other => other,
+        };
+
+        self.finish(&record.id, result)?;
+        self.load(&record.id)
+    }
+
+    fn claim_next(&self) -> Result<Option<(LocalJobRecord, Arc<AtomicBool>)>, LocalJobError> {
+        let mut running = self.running.lock().unwrap();
+        let conn = self.open()?;
+
+        loop {
+            let next = conn
+                .query_row(
+                    &format!(
+                        "SELECT {} FROM local_jobs WHERE status = ?1
+                         ORDER BY created_at, rowid LIMIT 1",
+                        RECORD_COLUMNS
+                    ),
+                    [LocalJobStatus::Pending.as_str()],
+                    read_row,
+                )
+                .optional()?;
+            let Some(raw) = next else {
+                return Ok(None);
+            };
+
+            let job_id = raw.0.clone();
+            let mut record = match decode_row(raw) {
+                Ok(record) => record,
+                Err(err) => {
+                    conn.execute(
+                        "UPDATE local_jobs SET status = ?2, ended_at = ?3, error = ?4
+                         WHERE id = ?1",
+                        params![
+                            job_id,
+                            LocalJobStatus::Failed.as_str(),
+                            now_ms(),
+                            err.to_string()
+                        ],
+                    )?;
+                    continue;
+                }
+            };
+
+            let started_at = now_ms();
+            conn.execute(
+                "UPDATE local_jobs SET status = ?2, started_at = ?3, error = NULL WHERE id = ?1",
+                params![job_id, LocalJobStatus::Running.as_str(), started_at],
+            )?;
+            record.status = LocalJobStatus::Running;
+            record.started_at = Some(started_at);
+
+            let cancel = Arc::new(AtomicBool::new(false));
+            *running = Some(RunningJob {
+                id: job_id,
+                cancel: Arc::clone(&cancel),
+            });
+            return Ok(Some((record, cancel)));
+        }
+    }
+
+    fn finish(
+        &self,
+        job_id: &str,
+        result: Result<PipelineOutput, LocalJobError>,
+    ) -> Result<(), LocalJobError> {
+        let conn = self.open()?;
+        let (stage, message) = match result {
+            Ok(output) => {
+                conn.execute(
+                    "UPDATE local_jobs SET status = ?2, ended_at = ?3, workspace = ?4,
+                     archive_path = ?5 WHERE id = ?1",
+                    params![
+                        job_id,
+                        LocalJobStatus::Completed.as_str(),
+                        now_ms(),
+                        output.workspace.to_string_lossy(),
+                        output.archive_path.to_string_lossy()
+                    ],
+                )?;
+                (UploadProgressStage::Completed, "处理完成".to_string())
+            }
+            Err(LocalJobError::Canceled) => {
+                conn.execute(
+                    "UPDATE local_jobs SET status = ?2, ended_at = ?3 WHERE id = ?1",
+                    params![job_id, LocalJobStatus::Canceled.as_str(), now_ms()],
+                )?;
+                (UploadProgressStage::Failed, "作业已取消".to_string())
+            }
+            Err(err) => {
+                let message = err.to_string();
+                conn.execute(
+                    "UPDATE local_jobs SET status = ?2, ended_at = ?3, error = ?4 WHERE id = ?1",
+                    params![job_id, LocalJobStatus::Failed.as_str(), now_ms(), message],
+                )?;
+                (
+                    UploadProgressStage::Failed,
+                    format!("处理失败: {}", message),
+                )
+            }
+        };
+
+        self.emit_final(job_id, stage, message);
+        Ok(())
+    }
+
+    fn execute_pipeline(
+        &self,
+        record: &LocalJobRecord,
+        cancel: &AtomicBool,
+    ) -> Result<PipelineOutput, LocalJobError> {
+        let config = &record.config;
+        let job_id = record.id.as_str();
+        let archive_path = resolve_archive_path(config, job_id);
+        let partial_path = partial_archive_path(&archive_path);
+        if partial_path.exists() {
+            fs::remove_file(&partial_path).map_err(UploadError::from)?;
+        }
+        if archive_path.exists() {
+            // The default location is named after the job, so anything there
+            // is stale output from an interrupted run of this same job.
+            if config.archive_path.is_some() {
+                return Err(LocalJobError::ArchiveExists(archive_path));
+            }
+            fs::remove_file(&archive_path).map_err(UploadError::from)?;
+        }
+
+        let mut split_progress = |payload: SplitProgress| {
+            self.emit(doublepage::SPLIT_PROGRESS_EVENT, job_id, payload);
+        };
+        let split = doublepage::prepare_split(
+            SplitCommandOptions {
+                directory: config.directory.clone(),
+                dry_run: false,
+                overwrite: true,
+                thresholds: None,
+            },
+            Some(&mut split_progress),
+        )?;
+        let workspace = split
+            .workspace_directory
+            .clone()
+            .ok_or_else(|| LocalJobError::WorkspaceMissing(config.directory.clone()))?;
+
+        let conn = self.open()?;
+        conn.execute(
+            "UPDATE local_jobs SET workspace = ?2 WHERE id = ?1",
+            params![job_id, workspace.to_string_lossy()],
+        )?;
+        drop(conn);
+        ensure_not_canceled(cancel)?;
+
+        let rename = manga::perform_rename(RenameOptions {
+            directory: config.directory.clone(),
+            pad: config.pad,
+            target_extension: config.target_extension.clone(),
+            dry_run: false,
+            split: RenameSplitOptions {
+                enabled: true,
+                workspace: Some(workspace.clone()),
+                report_path: split.report_path.clone(),
+                summary: Some(RenameSplitSummary {
+                    analyzed_files: split.analyzed_files,
+                    emitted_files: split.emitted_files,
+                    skipped_files: split.skipped_files,
+                    split_pages: split.split_pages,
+                    cover_trims: split.cover_trims,
+                    fallback_splits: split.fallback_splits,
+                }),
+                warnings: Some(split.warnings.clone()),
+            },
+        })?;
+        ensure_not_canceled(cancel)?;
+
+        if let Some(parent) = archive_path.parent() {
+            fs::create_dir_all(parent).map_err(UploadError::from)?;
+        }
+        let files = manga::collect_sorted_files(&rename.directory)?;
+        let written = manga::write_zip_archive(&partial_path, &files, |payload| {
+            self.emit(manga::UPLOAD_EVENT_NAME, job_id, payload);
+        })
+        .map_err(LocalJobError::from)
+        .and_then(|_| {
+            if config.archive_path.is_some() && archive_path.exists() {
+                return Err(LocalJobError::ArchiveExists(archive_path.clone()));
+            }
+            fs::rename(&partial_path, &archive_path).map_err(|err| UploadError::from(err).into())
+        });
+        if let Err(err) = written {
+            fs::remove_file(&partial_path).ok();
+            return Err(err);
+        }
+
+        Ok(PipelineOutput {
+            workspace,
+            archive_path,
+        })
+    }
+
+    fn recover_interrupted(&self) -> Result<usize, LocalJobError> {
+        let conn = self.open()?;
+        conn.execute(
+            "UPDATE local_jobs SET status = ?1, ended_at = ?2 WHERE status = ?3",
+            params![
+                LocalJobStatus::Canceled.as_str(),
+                now_ms(),
+                LocalJobStatus::Canceling.as_str()
+            ],
+        )?;
+        let count = conn.execute(
+            "UPDATE local_jobs SET status = ?1, started_at = NULL WHERE status = ?2",
+            params![
+                LocalJobStatus::Pending.as_str(),
+                LocalJobStatus::Running.as_str()
+            ],
+        )?;
+        Ok(count)
+    }
+
+    fn wake(&self) {
+        if let Some(sender) = self.wake.lock().unwrap().as_ref() {
+            let _ = sender.send(());
+        }
+    }
+
+    fn emit<T: Serialize + Clone>(&self, event: &str, job_id: &str, progress: T) {
+        if let Some(handle) = self.app.as_ref() {
+            let payload = LocalJobProgress {
+                local_job_id: job_id.to_string(),
+                progress,
+            };
+            let _ = handle.emit(event, payload);
+        }
+    }
+
+    fn emit_final(&self, job_id: &str, stage: UploadProgressStage, message: String) {
+        self.emit(
+            manga::UPLOAD_EVENT_NAME,
+            job_id,
+            UploadProgress {
+                stage,
+                transferred_bytes: 0,
+                total_bytes: 0,
+                processed_files: 0,
+                total_files: 0,
+                message: Some(message),
+            },
+        );
+    }
+
+    fn open(&self) -> Result<Connection, LocalJobError> {
+        Ok(Connection::open(&self.db_path)?)
+    }
+}
+
+const RECORD_COLUMNS: &str =
+    "id, status, config_json, created_at, started_at, ended_at, workspace, archive_path, error";
+
+type RawRow = (
+    String,
+    String,
+    String,
+    i64,
+    Option<i64>,
+    Option<i64>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+fn read_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<RawRow> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        row.get(5)?,
+        row.get(6)?,
+        row.get(7)?,
+        row.get(8)?,
+    ))
+}
+
+fn decode_row(raw: RawRow) -> Result<LocalJobRecord, LocalJobError> {
+    let (id, status, config_json, created_at, started_at, ended_at, workspace, archive, error) =
+        raw;
+    Ok(LocalJobRecord {
+        id,
+        status: LocalJobStatus::from_str(&status).ok_or(LocalJobError::InvalidStatus(status))?,
+        config: serde_json::from_str(&config_json)?,
+        created_at,
+        started_at,
+        ended_at,
+        workspace: workspace.map(PathBuf::from),
+        archive_path: archive.map(PathBuf::from),
+        error,
+    })
+}
+
+fn resolve_archive_path(config: &LocalPipelineConfig, job_id: &str) -> PathBuf {
+    match config.archive_path.as_ref() {
+        Some(path) => path.clone(),
+        None => config
+            .directory
+            .join(".rei_cache")
+            .join("localjobs")
+            .join(format!("{}.zip", job_id)),
+    }
+}
+
+/// Zip output is written next to the final archive and only moved into place
+/// once complete, so an interrupted write never looks like a finished archive.
+fn partial_archive_path(archive_path: &Path) -> PathBuf {
+    let mut name = archive_path.as_os_str().to_owned();
+    name.push(".partial");
+    PathBuf::from(name)
+}
+
+fn ensure_not_canceled(cancel: &AtomicBool) -> Result<(), LocalJobError> {
+    if cancel.load(Ordering::SeqCst) {
+        Err(LocalJobError::Canceled)
+    } else {
+        Ok(())
+    }
+}
+
+fn now_ms() -> i64 {
+    Utc::now().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("docs")
+            .join("assets")
+            .join("manga-content-aware-split")
+            .join("phase1_input")
+            .join(name)
+    }
+
+    fn setup_queue(temp: &TempDir) -> Arc<LocalJobQueue> {
+        let db_path = temp.path().join("app.db");
+        ensure_schema(&db_path).expect("schema");
+        Arc::new(LocalJobQueue::new(db_path, None))
+    }
+
+    fn setup_volume(temp: &TempDir, name: &str) -> PathBuf {
+        let directory = temp.path().join(name);
+        fs::create_dir_all(&directory).expect("create volume dir");
+        for fixture in ["double_page_story.png", "cover_layout.png"] {
+            fs::copy(fixture_path(fixture), directory.join(fixture)).expect("copy fixture");
+        }
+        directory
+    }
+
+    fn pipeline_config(directory: PathBuf) -> LocalPipelineConfig {
+        LocalPipelineConfig {
+            directory,
+            pad: 4,
+            target_extension: "png".to_string(),
+            archive_path: None,
+        }
+    }
+
+    fn wait_for_terminal(queue: &LocalJobQueue, ids: &[String]) -> Vec<LocalJobRecord> {
+        let deadline = Instant::now() + Duration::from_secs(120);
+        loop {
+            let records: Vec<LocalJobRecord> = ids
+                .iter()
+                .map(|id| queue.load(id).expect("load").expect("record"))
+                .collect();
+            let finished = records.iter().all(|record| {
+                !matches!(
+                    record.status,
+                    LocalJobStatus::Pending | LocalJobStatus::Running
+                )
+            });
+            if finished {
+                return records;
+            }
+            assert!(Instant::now() < deadline, "local jobs did not finish");
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn worker_completes_queued_jobs_with_workspace_and_archive() {
+        let temp = TempDir::new().expect("temp dir");
+        let queue = setup_queue(&temp);
+        let first = queue
+            .enqueue(pipeline_config(setup_volume(&temp, "vol01")))
+            .expect("enqueue first");
+        let second = queue
+            .enqueue(pipeline_config(setup_volume(&temp, "vol02")))
+            .expect("enqueue second");
+        let sibling = temp.path().join("vol01.zip");
+        fs::write(&sibling, b"keep").expect("write sibling archive");
+
+        queue.start();
+        let records = wait_for_terminal(&queue, &[first.id.clone(), second.id.clone()]);
+
+        for record in records {
+            assert_eq!(
+                record.status,
+                LocalJobStatus::Completed,
+                "{:?}",
+                record.error
+            );
+            let workspace = record.workspace.expect("workspace");
+            assert!(workspace.join("0001.png").exists());
+            assert!(workspace.join("manifest.json").exists());
+            let archive = record.archive_path.expect("archive path");
+            assert!(archive.exists());
+            assert!(archive.starts_with(record.config.directory.join(".rei_cache")));
+            let file = fs::File::open(&archive).expect("open archive");
+            let zip = zip::ZipArchive::new(file).expect("read archive");
+            assert!(zip.len() >= 2);
+        }
+        assert_eq!(fs::read(&sibling).expect("read sibling"), b"keep");
+    }
+
+    #[test]
+    fn cancel_between_jobs_skips_pending_job() {
+        let temp = TempDir::new().expect("temp dir");
+        let queue = setup_queue(&temp);
+        let first = queue
+            .enqueue(pipeline_config(setup_volume(&temp, "vol01")))
+            .expect("enqueue first");
+        let second = queue
+            .enqueue(pipeline_config(setup_volume(&temp, "vol02")))
+            .expect("enqueue second");
+
+        let finished = queue.run_next().expect("run first").expect("first record");
+        assert_eq!(finished.id, first.id);
+        assert_eq!(finished.status, LocalJobStatus::Completed);
+
+        let canceled = queue.cancel(&second.id).expect("cancel second");
+        assert_eq!(canceled.status, LocalJobStatus::Canceled);
+        assert!(queue.run_next().expect("run next").is_none());
+
+        let second = queue.load(&second.id).expect("load").expect("record");
+        assert_eq!(second.status, LocalJobStatus::Canceled);
+        assert!(second.started_at.is_none());
+        assert!(second.archive_path.is_none());
+        assert!(!second.config.directory.join(".rei_cache").exists());
+        assert!(matches!(
+            queue.cancel(&first.id),
+            Err(LocalJobError::NotCancelable(_))
+        ));
+    }
+
+    #[test]
+    fn interrupted_job_resumes_and_replaces_partial_archive() {
+        let temp = TempDir::new().expect("temp dir");
+        let queue = setup_queue(&temp);
+        let job = queue
+            .enqueue(pipeline_config(setup_volume(&temp, "vol01")))
+            .expect("enqueue");
+        let conn = Connection::open(temp.path().join("app.db")).expect("open db");
+        conn.execute(
+            "UPDATE local_jobs SET status = 'running', started_at = 1 WHERE id = ?1",
+            [job.id.as_str()],
+        )
+        .expect("mark running");
+        let stale = resolve_archive_path(&job.config, &job.id);
+        fs::create_dir_all(stale.parent().expect("archive parent")).expect("create cache dir");
+        fs::write(&stale, b"truncated").expect("write stale archive");
+        fs::write(partial_archive_path(&stale), b"truncated").expect("write partial archive");
+
+        let restarted = LocalJobQueue::new(temp.path().join("app.db"), None);
+        assert_eq!(restarted.recover_interrupted().expect("recover"), 1);
+        let record = restarted.load(&job.id).expect("load").expect("record");
+        assert_eq!(record.status, LocalJobStatus::Pending);
+        assert!(record.started_at.is_none());
+
+        let record = restarted.run_next().expect("run").expect("record");
+        assert_eq!(record.id, job.id);
+        assert_eq!(
+            record.status,
+            LocalJobStatus::Completed,
+            "{:?}",
+            record.error
+        );
+        let archive = record.archive_path.expect("archive path");
+        assert_eq!(archive, stale);
+        assert!(!partial_archive_path(&archive).exists());
+        let file = fs::File::open(&archive).expect("open archive");
+        let zip = zip::ZipArchive::new(file).expect("read archive");
+        assert!(zip.len() >= 2);
+    }
+
+    #[test]
+    fn existing_archive_is_not_overwritten() {
+        let temp = TempDir::new().expect("temp dir");
+        let queue = setup_queue(&temp);
+        let target = temp.path().join("vol01.zip");
+        fs::write(&target, b"keep").expect("write existing archive");
+        let mut config = pipeline_config(setup_volume(&temp, "vol01"));
+        config.archive_path = Some(target.clone());
+        let job = queue.enqueue(config).expect("enqueue");
+
+        let record = queue.run_next().expect("run").expect("record");
+        assert_eq!(record.id, job.id);
+        assert_eq!(record.status, LocalJobStatus::Failed);
+        assert!(record
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("archive already exists"));
+        assert!(record.archive_path.is_none());
+        assert_eq!(fs::read(&target).expect("read archive"), b"keep");
+    }
+
+    #[test]
+    fn cancel_running_job_marks_canceling() {
+        let temp = TempDir::new().expect("temp dir");
+        let queue = setup_queue(&temp);
+        let job = queue
+            .enqueue(pipeline_config(temp.path().join("vol01")))
+            .expect("enqueue");
+        let (claimed, cancel) = queue.claim_next().expect("claim").expect("claimed job");
+        assert_eq!(claimed.id, job.id);
+
+        let record = queue.cancel(&job.id).expect("cancel running");
+        assert_eq!(record.status, LocalJobStatus::Canceling);
+        assert!(cancel.load(Ordering::SeqCst));
+
+        queue.running.lock().unwrap().take();
+        queue
+            .finish(&job.id, Err(LocalJobError::Canceled))
+            .expect("finish");
+        let record = queue.load(&job.id).expect("load").expect("record");
+        assert_eq!(record.status, LocalJobStatus::Canceled);
+    }
+
+    #[test]
+    fn unknown_status_is_reported_as_decode_error() {
+        let temp = TempDir::new().expect("temp dir");
+        let queue = setup_queue(&temp);
+        let job = queue
+            .enqueue(pipeline_config(temp.path().join("vol01")))
+            .expect("enqueue");
+        let conn = Connection::open(temp.path().join("app.db")).expect("open db");
+        conn.execute(
+            "UPDATE local_jobs SET status = 'bogus' WHERE id = ?1",
+            [job.id.as_str()],
+        )
+        .expect("corrupt status");
+
+        assert!(matches!(
+            queue.load(&job.id),
+            Err(LocalJobError::InvalidStatus(status)) if status == "bogus"
+        ));
+    }
+}
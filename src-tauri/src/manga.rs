@@ -39,6 +39,11 @@ const SUPPORTED_IMAGE_EXTENSIONS: &[&str] =
     &["jpg", "jpeg", "png", "webp", "bmp", "tif", "tiff", "gif"];
 pub const JOB_EVENT_NAME: &str = "manga-job-event";
 pub const UPLOAD_EVENT_NAME: &str = "manga-upload-progress";
+const TITLE_HEADER_NAME: &str = "X-Rei-Title";
+const VOLUME_HEADER_NAME: &str = "X-Rei-Volume";
+const META_FILE_SUFFIX: &str = ".meta.json";
+const META_FILE_NAME: &str = "meta.json";
+const MAX_METADATA_HEADER_LENGTH: usize = 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -141,11 +146,11 @@ pub struct RenameSplitOptions {
     pub warnings: Option<Vec<String>>,
 }
 
-fn default_pad() -> usize {
+pub(crate) fn default_pad() -> usize {
     4
 }
 
-fn default_extension() -> String {
+pub(crate) fn default_extension() -> String {
     "jpg".to_string()
 }
 
@@ -778,6 +783,8 @@ pub struct UploadRequest {
     pub bearer_token: Option<String>,
     #[serde(default)]
     pub metadata: Option<UploadMetadata>,
+    #[serde(default)]
+    pub emit_meta_file: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -796,6 +803,8 @@ pub struct UploadOutcome {
     pub uploaded_bytes: u64,
     pub file_count: usize,
     pub mode: UploadMode,
+    pub meta_remote_path: Option<String>,
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -845,6 +854,8 @@ pub struct JobInputPayload {
     #[serde(rename = "type")]
     pub kind: String,
     pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1308,6 +1319,8 @@ pub enum UploadError {
     NonUtf8Path(PathBuf),
     UnexpectedStatus(StatusCode),
     UnsupportedMode,
+    InvalidHeaderValue(&'static str),
+    Serialization(serde_json::Error),
 }
 
 impl fmt::Display for UploadError {
@@ -1329,6 +1342,14 @@ impl fmt::Display for UploadError {
                 write!(f, "unexpected response status: {}", status)
             }
             UploadError::UnsupportedMode => write!(f, "unsupported upload mode"),
+            UploadError::InvalidHeaderValue(name) => {
+                write!(
+                    f,
+                    "metadata for header {} contains control characters or exceeds {} bytes",
+                    name, MAX_METADATA_HEADER_LENGTH
+                )
+            }
+            UploadError::Serialization(err) => write!(f, "failed to encode metadata: {}", err),
         }
     }
 }
@@ -1353,6 +1374,12 @@ impl From<reqwest::Error> for UploadError {
     }
 }
 
+impl From<serde_json::Error> for UploadError {
+    fn from(value: serde_json::Error) -> Self {
+        UploadError::Serialization(value)
+    }
+}
+
 pub fn perform_upload(
     app: Option<AppHandle>,
     request: UploadRequest,
@@ -1364,6 +1391,7 @@ pub fn perform_upload(
         mode,
         bearer_token,
         metadata,
+        emit_meta_file,
     } = request;
 
     if !local_path.exists() || !local_path.is_dir() {
@@ -1376,6 +1404,14 @@ pub fn perform_upload(
     }
 
     let remote_url = build_remote_url(&service_url, &remote_path);
+    let metadata = metadata.as_ref().and_then(normalize_metadata);
+    let meta_target = if emit_meta_file && metadata.is_some() {
+        let meta_path = build_meta_remote_path(&remote_path);
+        let meta_url = build_remote_url(&service_url, &meta_path);
+        Some((meta_path, meta_url))
+    } else {
+        None
+    };
 
     match mode {
         UploadMode::Zip => upload_as_zip(
@@ -1384,6 +1420,7 @@ pub fn perform_upload(
             &files,
             bearer_token.as_deref(),
             metadata.as_ref(),
+            meta_target,
         ),
         UploadMode::Folder => Err(UploadError::UnsupportedMode),
     }
@@ -2102,7 +2139,9 @@ fn is_terminal_status(status: &str) -> bool {
     matches!(status, "SUCCESS" | "FAILED")
 }
 
-fn collect_sorted_files(directory: &Path) -> Result<Vec<(PathBuf, String)>, UploadError> {
+pub(crate) fn collect_sorted_files(
+    directory: &Path,
+) -> Result<Vec<(PathBuf, String)>, UploadError> {
     let mut files = Vec::new();
 
     for entry in fs::read_dir(directory)? {
@@ -2618,7 +2657,12 @@ fn upload_as_zip(
     files: &[(PathBuf, String)],
     bearer_token: Option<&str>,
     metadata: Option<&UploadMetadata>,
+    meta_target: Option<(String, String)>,
 ) -> Result<UploadOutcome, UploadError> {
+    let metadata_headers = match metadata {
+        Some(meta) => build_metadata_headers(meta)?,
+        None => Vec::new(),
+    };
     let file_count = files.len();
     emit_upload_event(
         app.as_ref(),
@@ -2646,13 +2690,8 @@ fn upload_as_zip(
         request = request.bearer_auth(token);
     }
 
-    if let Some(meta) = metadata {
-        if let Some(title) = meta.title.as_deref() {
-            request = request.header("X-Reichan-Title", title);
-        }
-        if let Some(volume) = meta.volume.as_deref() {
-            request = request.header("X-Reichan-Volume", volume);
-        }
+    for (name, value) in metadata_headers {
+        request = request.header(name, value);
     }
 
     let response = request.body(reqwest::blocking::Body::new(reader)).send();
@@ -2692,6 +2731,17 @@ fn upload_as_zip(
         }
     }
 
+    fs::remove_file(&zip_path).ok();
+
+    let mut meta_remote_path = None;
+    let mut warnings = Vec::new();
+    if let (Some((meta_path, meta_url)), Some(meta)) = (meta_target, metadata) {
+        match upload_meta_file(&client, &meta_url, bearer_token, meta) {
+            Ok(()) => meta_remote_path = Some(meta_path),
+            Err(err) => warnings.push(format!("压缩包已上传，但元数据上传失败: {}", err)),
+        }
+    }
+
     emit_upload_event(
         app.as_ref(),
         UploadProgress {
@@ -2704,8 +2754,6 @@ fn upload_as_zip(
         },
     );
 
-    fs::remove_file(&zip_path).ok();
-
     emit_upload_event(
         app.as_ref(),
         UploadProgress {
@@ -2723,9 +2771,109 @@ fn upload_as_zip(
         uploaded_bytes: zipped_bytes,
         file_count,
         mode: UploadMode::Zip,
+        meta_remote_path,
+        warnings,
     })
 }
 
+fn upload_meta_file(
+    client: &Client,
+    meta_url: &str,
+    bearer_token: Option<&str>,
+    metadata: &UploadMetadata,
+) -> Result<(), UploadError> {
+    let body = serde_json::to_vec(metadata)?;
+    let mut request = client
+        .put(meta_url)
+        .header("Content-Type", "application/json");
+
+    if let Some(token) = bearer_token {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request.body(body).send()?;
+    if !response.status().is_success() {
+        return Err(UploadError::UnexpectedStatus(response.status()));
+    }
+    Ok(())
+}
+
+/// Trims every field and drops blank ones so the headers and meta.json carry
+/// the same values. Returns `None` when no field is left.
+fn normalize_metadata(metadata: &UploadMetadata) -> Option<UploadMetadata> {
+    let normalize = |value: Option<&str>| {
+        value
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let normalized = UploadMetadata {
+        title: normalize(metadata.title.as_deref()),
+        volume: normalize(metadata.volume.as_deref()),
+    };
+    if normalized.title.is_none() && normalized.volume.is_none() {
+        None
+    } else {
+        Some(normalized)
+    }
+}
+
+fn build_metadata_headers(
+    metadata: &UploadMetadata,
+) -> Result<Vec<(&'static str, HeaderValue)>, UploadError> {
+    let mut headers = Vec::new();
+    let fields = [
+        (TITLE_HEADER_NAME, metadata.title.as_deref()),
+        (VOLUME_HEADER_NAME, metadata.volume.as_deref()),
+    ];
+
+    for (name, raw) in fields {
+        let Some(raw) = raw else {
+            continue;
+        };
+        if raw.chars().any(char::is_control) {
+            return Err(UploadError::InvalidHeaderValue(name));
+        }
+        let encoded = percent_encode_header_value(raw);
+        if encoded.len() > MAX_METADATA_HEADER_LENGTH {
+            return Err(UploadError::InvalidHeaderValue(name));
+        }
+        let value =
+            HeaderValue::from_str(&encoded).map_err(|_| UploadError::InvalidHeaderValue(name))?;
+        headers.push((name, value));
+    }
+
+    Ok(headers)
+}
+
+/// Percent-encodes everything outside the RFC 3986 unreserved set so that
+/// non-ASCII titles survive as plain ASCII header content.
+fn percent_encode_header_value(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn build_meta_remote_path(remote_path: &str) -> String {
+    if remote_path.is_empty() || remote_path.ends_with('/') {
+        return format!("{}{}", remote_path, META_FILE_NAME);
+    }
+
+    let stem = if remote_path.to_ascii_lowercase().ends_with(".zip") {
+        &remote_path[..remote_path.len() - ".zip".len()]
+    } else {
+        remote_path
+    };
+    format!("{}{}", stem, META_FILE_SUFFIX)
+}
+
 fn create_zip_archive_with_progress(
     app: Option<&AppHandle>,
     files: &[(PathBuf, String)],
@@ -2734,7 +2882,19 @@ fn create_zip_archive_with_progress(
     let file_name = format!("rei-manga-{}-{}.zip", std::process::id(), timestamp);
     let temp_path = std::env::temp_dir().join(file_name);
 
-    let file = File::create(&temp_path)?;
+    let size = write_zip_archive(&temp_path, files, |progress| {
+        emit_upload_event(app, progress)
+    })?;
+
+    Ok((temp_path, size))
+}
+
+pub(crate) fn write_zip_archive(
+    target: &Path,
+    files: &[(PathBuf, String)],
+    mut on_progress: impl FnMut(UploadProgress),
+) -> Result<u64, UploadError> {
+    let file = File::create(target)?;
     let mut writer = zip::ZipWriter::new(file);
     let options = FileOptions::default()
         .compression_method(CompressionMethod::Deflated)
@@ -2745,24 +2905,21 @@ fn create_zip_archive_with_progress(
         writer.start_file(name, options)?;
         let mut source = File::open(path)?;
         io::copy(&mut source, &mut writer)?;
-        emit_upload_event(
-            app,
-            UploadProgress {
-                stage: UploadProgressStage::Preparing,
-                transferred_bytes: 0,
-                total_bytes: 0,
-                processed_files: index + 1,
-                total_files,
-                message: Some(format!("已打包 {}/{}", index + 1, total_files)),
-            },
-        );
+        on_progress(UploadProgress {
+            stage: UploadProgressStage::Preparing,
+            transferred_bytes: 0,
+            total_bytes: 0,
+            processed_files: index + 1,
+            total_files,
+            message: Some(format!("已打包 {}/{}", index + 1, total_files)),
+        });
     }
 
     let file = writer.finish()?;
     let size = file.metadata()?.len();
     drop(file);
 
-    Ok((temp_path, size))
+    Ok(size)
 }
 
 fn build_remote_url(base: &str, path: &str) -> String {
@@ -3104,6 +3261,7 @@ mod tests {
                     title: Some("Title".to_string()),
                     volume: Some("Volume".to_string()),
                 }),
+                emit_meta_file: false,
             },
         )
         .expect("upload result");
@@ -3116,6 +3274,270 @@ mod tests {
         assert_eq!(result.file_count, 2);
         assert_eq!(result.mode, UploadMode::Zip);
         assert!(result.uploaded_bytes > 0);
+        assert!(result.meta_remote_path.is_none());
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn upload_sends_encoded_metadata_headers_and_meta_file() {
+        let temp = TempDir::new().expect("temp dir");
+        write_file(temp.path(), "a.jpg");
+
+        let server = MockServer::start();
+        let archive_mock = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/incoming/shingeki-0001.zip")
+                .header(
+                    "x-rei-title",
+                    "%E9%80%B2%E6%92%83%E3%81%AE%E5%B7%A8%E4%BA%BA",
+                )
+                .header("x-rei-volume", "%E7%AC%AC%201%20%E5%B7%BB");
+            then.status(201).body("ok");
+        });
+        let meta_mock = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/incoming/shingeki-0001.meta.json")
+                .header("content-type", "application/json")
+                .json_body(json!({"title": "進撃の巨人", "volume": "第 1 巻"}));
+            then.status(201).body("ok");
+        });
+
+        let result = perform_upload(
+            None,
+            UploadRequest {
+                service_url: server.url(""),
+                remote_path: "/incoming/shingeki-0001.zip".to_string(),
+                local_path: temp.path().to_path_buf(),
+                mode: UploadMode::Zip,
+                bearer_token: None,
+                metadata: Some(UploadMetadata {
+                    title: Some("進撃の巨人".to_string()),
+                    volume: Some("第 1 巻".to_string()),
+                }),
+                emit_meta_file: true,
+            },
+        )
+        .expect("upload result");
+
+        archive_mock.assert();
+        meta_mock.assert();
+        assert_eq!(
+            result.meta_remote_path.as_deref(),
+            Some("/incoming/shingeki-0001.meta.json")
+        );
+    }
+
+    #[test]
+    fn upload_rejects_control_characters_in_metadata() {
+        let temp = TempDir::new().expect("temp dir");
+        write_file(temp.path(), "a.jpg");
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(201).body("ok");
+        });
+
+        let result = perform_upload(
+            None,
+            UploadRequest {
+                service_url: server.url(""),
+                remote_path: "/incoming/title-volume.zip".to_string(),
+                local_path: temp.path().to_path_buf(),
+                mode: UploadMode::Zip,
+                bearer_token: None,
+                metadata: Some(UploadMetadata {
+                    title: Some("Title\r\nX-Injected: yes".to_string()),
+                    volume: None,
+                }),
+                emit_meta_file: true,
+            },
+        );
+
+        assert!(matches!(
+            result,
+            Err(UploadError::InvalidHeaderValue(TITLE_HEADER_NAME))
+        ));
+        mock.assert_hits(0);
+    }
+
+    #[test]
+    fn upload_reports_warning_when_meta_file_fails() {
+        let temp = TempDir::new().expect("temp dir");
+        write_file(temp.path(), "a.jpg");
+
+        let server = MockServer::start();
+        let archive_mock = server.mock(|when, then| {
+            when.method(PUT).path("/incoming/title-volume.zip");
+            then.status(201).body("ok");
+        });
+        let meta_mock = server.mock(|when, then| {
+            when.method(PUT).path("/incoming/title-volume.meta.json");
+            then.status(500).body("boom");
+        });
+
+        let result = perform_upload(
+            None,
+            UploadRequest {
+                service_url: server.url(""),
+                remote_path: "/incoming/title-volume.zip".to_string(),
+                local_path: temp.path().to_path_buf(),
+                mode: UploadMode::Zip,
+                bearer_token: None,
+                metadata: Some(UploadMetadata {
+                    title: Some("Title".to_string()),
+                    volume: Some("1".to_string()),
+                }),
+                emit_meta_file: true,
+            },
+        )
+        .expect("archive upload should still succeed");
+
+        archive_mock.assert();
+        meta_mock.assert();
+        assert!(result.meta_remote_path.is_none());
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn meta_file_uses_trimmed_metadata() {
+        let temp = TempDir::new().expect("temp dir");
+        write_file(temp.path(), "a.jpg");
+
+        let server = MockServer::start();
+        let archive_mock = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/incoming/title-volume.zip")
+                .header("x-rei-title", "Title");
+            then.status(201).body("ok");
+        });
+        let meta_mock = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/incoming/title-volume.meta.json")
+                .json_body(json!({"title": "Title", "volume": null}));
+            then.status(201).body("ok");
+        });
+
+        let result = perform_upload(
+            None,
+            UploadRequest {
+                service_url: server.url(""),
+                remote_path: "/incoming/title-volume.zip".to_string(),
+                local_path: temp.path().to_path_buf(),
+                mode: UploadMode::Zip,
+                bearer_token: None,
+                metadata: Some(UploadMetadata {
+                    title: Some("  Title ".to_string()),
+                    volume: Some("   ".to_string()),
+                }),
+                emit_meta_file: true,
+            },
+        )
+        .expect("upload result");
+
+        archive_mock.assert();
+        meta_mock.assert();
+        assert_eq!(
+            result.meta_remote_path.as_deref(),
+            Some("/incoming/title-volume.meta.json")
+        );
+    }
+
+    #[test]
+    fn blank_metadata_skips_meta_file() {
+        let temp = TempDir::new().expect("temp dir");
+        write_file(temp.path(), "a.jpg");
+
+        let server = MockServer::start();
+        let archive_mock = server.mock(|when, then| {
+            when.method(PUT).path("/incoming/title-volume.zip");
+            then.status(201).body("ok");
+        });
+        let meta_mock = server.mock(|when, then| {
+            when.method(PUT).path("/incoming/title-volume.meta.json");
+            then.status(201).body("ok");
+        });
+
+        let result = perform_upload(
+            None,
+            UploadRequest {
+                service_url: server.url(""),
+                remote_path: "/incoming/title-volume.zip".to_string(),
+                local_path: temp.path().to_path_buf(),
+                mode: UploadMode::Zip,
+                bearer_token: None,
+                metadata: Some(UploadMetadata {
+                    title: Some(" ".to_string()),
+                    volume: None,
+                }),
+                emit_meta_file: true,
+            },
+        )
+        .expect("upload result");
+
+        archive_mock.assert();
+        meta_mock.assert_hits(0);
+        assert!(result.meta_remote_path.is_none());
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn meta_remote_path_follows_archive_or_directory() {
+        assert_eq!(
+            build_meta_remote_path("/incoming/title-0001.zip"),
+            "/incoming/title-0001.meta.json"
+        );
+        assert_eq!(
+            build_meta_remote_path("/incoming/title-0001.ZIP"),
+            "/incoming/title-0001.meta.json"
+        );
+        assert_eq!(
+            build_meta_remote_path("/incoming/title-0001"),
+            "/incoming/title-0001.meta.json"
+        );
+        assert_eq!(build_meta_remote_path("/incoming/"), "/incoming/meta.json");
+        assert_eq!(build_meta_remote_path(""), "meta.json");
+    }
+
+    #[test]
+    fn create_remote_job_posts_meta_path() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/jobs").json_body(json!({
+                "title": "Title",
+                "volume": "Vol",
+                "input": {
+                    "type": "zip",
+                    "path": "incoming/file.zip",
+                    "metaPath": "incoming/file.meta.json"
+                },
+                "params": {
+                    "scale": 2,
+                    "model": "RealESRGAN_x4plus_anime_6B",
+                    "denoise": "medium"
+                }
+            }));
+            then.status(202).json_body(json!({"job_id": "meta"}));
+        });
+
+        let submission = create_remote_job(CreateJobOptions {
+            service_url: server.url(""),
+            bearer_token: None,
+            payload: JobPayload {
+                title: "Title".to_string(),
+                volume: "Vol".to_string(),
+                input: JobInputPayload {
+                    kind: "zip".to_string(),
+                    path: "incoming/file.zip".to_string(),
+                    meta_path: Some("incoming/file.meta.json".to_string()),
+                },
+                params: JobParamsPayload::default(),
+            },
+        })
+        .expect("submission");
+
+        assert_eq!(submission.job_id, "meta");
+        mock.assert();
     }
 
     #[test]
@@ -3147,6 +3569,7 @@ mod tests {
                 input: JobInputPayload {
                     kind: "zip".to_string(),
                     path: "incoming/file.zip".to_string(),
+                    meta_path: None,
                 },
                 params: JobParamsPayload::default(),
             },
@@ -272,6 +272,8 @@ type UploadOutcome = {
   uploadedBytes: number;
   fileCount: number;
   mode: UploadMode;
+  metaRemotePath?: string | null;
+  warnings?: string[];
 };
 
 type UploadProgressStage =
@@ -288,6 +290,7 @@ type UploadProgressPayload = {
   processedFiles: number;
   totalFiles: number;
   message?: string | null;
+  localJobId?: string | null;
 };
 
 type RenameFormState = {
@@ -302,6 +305,7 @@ type UploadFormState = {
   title: string;
   volume: string;
   mode: UploadMode;
+  emitMetaFile: boolean;
 };
 
 type ServiceAddressBook = {
@@ -510,6 +514,7 @@ type JobFormState = {
   volume: string;
   inputType: 'zip' | 'folder';
   inputPath: string;
+  metaPath: string;
   pollIntervalMs: number;
 };
 
@@ -534,6 +539,7 @@ type SplitProgressPayload = {
   processedFiles: number;
   currentFile?: string | null;
   stage: SplitProgressStage;
+  localJobId?: string | null;
 };
 
 type MangaSourceAnalysis = {
@@ -619,6 +625,7 @@ const createInitialUploadForm = (): UploadFormState => ({
   title: '',
   volume: '',
   mode: 'zip',
+  emitMetaFile: false,
 });
 
 const createInitialJobForm = (): JobFormState => ({
@@ -628,6 +635,7 @@ const createInitialJobForm = (): JobFormState => ({
   volume: '',
   inputType: 'zip',
   inputPath: '',
+  metaPath: '',
   pollIntervalMs: DEFAULT_POLL_INTERVAL,
 });
 
@@ -1354,6 +1362,7 @@ const MangaUpscaleAgent = () => {
       serviceUrl: prev.serviceUrl,
       bearerToken: prev.bearerToken,
       mode: prev.mode,
+      emitMetaFile: prev.emitMetaFile,
       title: prev.title,
     }));
     setUploadLoading(false);
@@ -2794,7 +2803,7 @@ const MangaUpscaleAgent = () => {
           'manga-upload-progress',
           (event) => {
             const payload = event.payload;
-            if (!payload) {
+            if (!payload || payload.localJobId) {
               return;
             }
 
@@ -2863,7 +2872,7 @@ const MangaUpscaleAgent = () => {
         const splitUnlisten = await listen<SplitProgressPayload>(
           'doublepage-split-progress',
           (event) => {
-            if (!event.payload) {
+            if (!event.payload || event.payload.localJobId) {
               return;
             }
             setSplitProgress(event.payload);
@@ -3773,6 +3782,9 @@ const MangaUpscaleAgent = () => {
 
       if (Object.keys(metadataEntries).length > 0) {
         request.metadata = metadataEntries;
+        if (uploadForm.emitMetaFile) {
+          request.emitMetaFile = true;
+        }
       }
 
       const result = await invoke<UploadOutcome>('upload_copyparty', {
@@ -3788,9 +3800,14 @@ const MangaUpscaleAgent = () => {
         title: trimmedTitle || prev.title,
         volume: trimmedVolume || prev.volume,
         inputPath: remotePath,
+        metaPath: result.metaRemotePath ?? '',
       }));
+      const warningSuffix =
+        result.warnings && result.warnings.length > 0
+          ? `（${result.warnings.join('；')}）`
+          : '';
       setUploadStatus(
-        `上传完成：${result.fileCount} 个文件，约 ${sizeInMb} MB，remote = ${result.remoteUrl}`
+        `上传完成：${result.fileCount} 个文件，约 ${sizeInMb} MB，remote = ${result.remoteUrl}${warningSuffix}`
       );
       setRemotePathSeed(Date.now());
     } catch (error) {
@@ -4032,6 +4049,7 @@ const MangaUpscaleAgent = () => {
     const title = jobForm.title.trim();
     const volume = jobForm.volume.trim();
     const inputPath = jobForm.inputPath.trim();
+    const metaPath = jobForm.metaPath.trim();
     const bearer = jobForm.bearerToken.trim();
 
     const readiness = assessManualReadiness();
@@ -4083,6 +4101,7 @@ const MangaUpscaleAgent = () => {
           input: {
             type: jobForm.inputType,
             path: inputPath,
+            ...(metaPath ? { metaPath } : {}),
           },
           params: paramsPayload,
         },
@@ -5005,6 +5024,21 @@ const MangaUpscaleAgent = () => {
               />
             </label>
 
+            <label className="form-field compact">
+              <span className="field-label">同时上传 meta.json</span>
+              <input
+                type="checkbox"
+                checked={uploadForm.emitMetaFile}
+                onChange={(event) => {
+                  const checked = event.currentTarget.checked;
+                  setUploadForm((prev) => ({
+                    ...prev,
+                    emitMetaFile: checked,
+                  }));
+                }}
+              />
+            </label>
+
             {isMultiVolumeSource && volumeMappings.length > 0 && (
               <label className="form-field compact">
                 <span className="field-label">上传卷</span>